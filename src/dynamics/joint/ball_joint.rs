@@ -0,0 +1,29 @@
+use super::JointParams;
+use crate::math::{Point, Real};
+
+/// A ball joint constrains two bodies to rotate freely around a common anchor point.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct BallJoint {
+    /// The anchor point on the first body, expressed in its local space.
+    pub local_anchor1: Point<Real>,
+    /// The anchor point on the second body, expressed in its local space.
+    pub local_anchor2: Point<Real>,
+}
+
+impl BallJoint {
+    /// Creates a new ball joint with the given anchors, expressed in the local space of
+    /// each of the affected bodies.
+    pub fn new(local_anchor1: Point<Real>, local_anchor2: Point<Real>) -> Self {
+        Self {
+            local_anchor1,
+            local_anchor2,
+        }
+    }
+}
+
+impl From<BallJoint> for JointParams {
+    fn from(joint: BallJoint) -> JointParams {
+        JointParams::BallJoint(joint)
+    }
+}
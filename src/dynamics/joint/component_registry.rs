@@ -0,0 +1,131 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// A single densely-packed, type-erased column of per-joint component data, indexed directly
+/// by a joint's arena slot (see `JointHandle::into_raw_parts`).
+trait ComponentColumn: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn remove(&mut self, slot: u32);
+    fn clone_box(&self) -> Box<dyn ComponentColumn>;
+}
+
+struct Column<T>(Vec<Option<T>>);
+
+impl<T: Clone + 'static> ComponentColumn for Column<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove(&mut self, slot: u32) {
+        if let Some(entry) = self.0.get_mut(slot as usize) {
+            *entry = None;
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn ComponentColumn> {
+        Box::new(Column(self.0.clone()))
+    }
+}
+
+impl Clone for Box<dyn ComponentColumn> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Type-erased, per-joint component storage.
+///
+/// Borrows the component-registry pattern from data-oriented physics engines: a type must be
+/// `register`ed once, after which values can be attached to any joint by its arena slot without
+/// the caller maintaining its own side map keyed by `JointHandle`. Each registered type gets its
+/// own densely-packed column, so iterating all joints' components of a given type stays
+/// cache-friendly and lookups are `O(1)`.
+pub(crate) struct JointComponents {
+    columns: HashMap<TypeId, Box<dyn ComponentColumn>>,
+}
+
+impl JointComponents {
+    pub fn new() -> Self {
+        Self {
+            columns: HashMap::new(),
+        }
+    }
+
+    /// Registers `T` as a component type, creating its (initially empty) column.
+    ///
+    /// Calling this more than once for the same `T` is harmless.
+    pub fn register<T: Clone + 'static>(&mut self) {
+        self.columns
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Column::<T>(Vec::new())));
+    }
+
+    /// Sets the `T` component of the joint at the given arena slot.
+    ///
+    /// Does nothing if `T` was never `register`ed.
+    pub fn set<T: Clone + 'static>(&mut self, slot: u32, value: T) {
+        if let Some(column) = self.columns.get_mut(&TypeId::of::<T>()) {
+            let column = column
+                .as_any_mut()
+                .downcast_mut::<Column<T>>()
+                .expect("component column type mismatch");
+            if slot as usize >= column.0.len() {
+                column.0.resize_with(slot as usize + 1, || None);
+            }
+            column.0[slot as usize] = Some(value);
+        }
+    }
+
+    /// Gets the `T` component of the joint at the given arena slot.
+    pub fn get<T: Clone + 'static>(&self, slot: u32) -> Option<&T> {
+        self.columns
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref::<Column<T>>()
+            .expect("component column type mismatch")
+            .0
+            .get(slot as usize)?
+            .as_ref()
+    }
+
+    /// Gets a mutable reference to the `T` component of the joint at the given arena slot.
+    pub fn get_mut<T: Clone + 'static>(&mut self, slot: u32) -> Option<&mut T> {
+        self.columns
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut::<Column<T>>()
+            .expect("component column type mismatch")
+            .0
+            .get_mut(slot as usize)?
+            .as_mut()
+    }
+
+    /// Clears every registered component of the joint at the given arena slot.
+    ///
+    /// Must be called whenever a joint is removed, since the slot it occupied may later be
+    /// recycled for an unrelated joint.
+    pub fn remove_slot(&mut self, slot: u32) {
+        for column in self.columns.values_mut() {
+            column.remove(slot);
+        }
+    }
+}
+
+impl Clone for JointComponents {
+    fn clone(&self) -> Self {
+        Self {
+            columns: self.columns.clone(),
+        }
+    }
+}
+
+impl Default for JointComponents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
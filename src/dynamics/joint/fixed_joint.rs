@@ -0,0 +1,29 @@
+use super::JointParams;
+use crate::math::{Isometry, Real};
+
+/// A fixed joint constrains two bodies to maintain a constant relative position and orientation.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct FixedJoint {
+    /// The joint's anchor frame on the first body, expressed in its local space.
+    pub local_frame1: Isometry<Real>,
+    /// The joint's anchor frame on the second body, expressed in its local space.
+    pub local_frame2: Isometry<Real>,
+}
+
+impl FixedJoint {
+    /// Creates a new fixed joint with the given anchor frames, expressed in the local space
+    /// of each of the affected bodies.
+    pub fn new(local_frame1: Isometry<Real>, local_frame2: Isometry<Real>) -> Self {
+        Self {
+            local_frame1,
+            local_frame2,
+        }
+    }
+}
+
+impl From<FixedJoint> for JointParams {
+    fn from(joint: FixedJoint) -> JointParams {
+        JointParams::FixedJoint(joint)
+    }
+}
@@ -0,0 +1,163 @@
+use super::{
+    BallJoint, FixedJoint, JointConstraintGenerator, JointHandle, PrismaticJoint, RevoluteJoint,
+};
+use crate::dynamics::RigidBodyHandle;
+use crate::math::Real;
+
+/// The parameters of a joint, describing how two bodies are constrained relative to each other.
+///
+/// This does not derive `Serialize`/`Deserialize`: a `#[serde(skip)]`'d `Custom` variant would
+/// still compile, but fail at runtime with an opaque error (and panic wherever a caller
+/// `.unwrap()`s the result, which is the common pattern) the moment a `JointSet` containing one
+/// is actually serialized. See the hand-written impls below instead.
+#[derive(Clone)]
+pub enum JointParams {
+    /// A ball joint, constraining two anchor points to coincide.
+    BallJoint(BallJoint),
+    /// A fixed joint, constraining two anchor frames to coincide.
+    FixedJoint(FixedJoint),
+    /// A prismatic joint, letting one body slide relative to the other along a single axis.
+    PrismaticJoint(PrismaticJoint),
+    /// A revolute joint, letting one body rotate relative to the other around a single axis.
+    RevoluteJoint(RevoluteJoint),
+    /// A user-defined joint, whose constraint rows are produced by a [`JointConstraintGenerator`].
+    ///
+    /// This lets users express holonomic constraints (gears, racks, curve-following sliders,
+    /// soft springs, ...) without forking the solver: the generator is consulted by the velocity
+    /// and position-stabilization passes exactly like any of the built-in joint types above.
+    ///
+    /// A trait object has no serializable representation, so this variant fails to serialize
+    /// with a clear error (see the `Serialize` impl below) instead of silently succeeding with
+    /// data loss.
+    Custom(Box<dyn JointConstraintGenerator>),
+}
+
+impl Clone for Box<dyn JointConstraintGenerator> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl serde::Serialize for JointParams {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            JointParams::BallJoint(joint) => {
+                serializer.serialize_newtype_variant("JointParams", 0, "BallJoint", joint)
+            }
+            JointParams::FixedJoint(joint) => {
+                serializer.serialize_newtype_variant("JointParams", 1, "FixedJoint", joint)
+            }
+            JointParams::PrismaticJoint(joint) => {
+                serializer.serialize_newtype_variant("JointParams", 2, "PrismaticJoint", joint)
+            }
+            JointParams::RevoluteJoint(joint) => {
+                serializer.serialize_newtype_variant("JointParams", 3, "RevoluteJoint", joint)
+            }
+            JointParams::Custom(_) => Err(serde::ser::Error::custom(
+                "a `JointParams::Custom` cannot be serialized: its `JointConstraintGenerator` \
+                 is a trait object with no serializable representation",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<'de> serde::Deserialize<'de> for JointParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // `Custom` is intentionally absent: it can never have been serialized in the first
+        // place (see the `Serialize` impl above), so there is nothing for it to round-trip.
+        #[derive(serde::Deserialize)]
+        enum Repr {
+            BallJoint(BallJoint),
+            FixedJoint(FixedJoint),
+            PrismaticJoint(PrismaticJoint),
+            RevoluteJoint(RevoluteJoint),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::BallJoint(joint) => JointParams::BallJoint(joint),
+            Repr::FixedJoint(joint) => JointParams::FixedJoint(joint),
+            Repr::PrismaticJoint(joint) => JointParams::PrismaticJoint(joint),
+            Repr::RevoluteJoint(joint) => JointParams::RevoluteJoint(joint),
+        })
+    }
+}
+
+/// A joint attaches two rigid-bodies together according to some [`JointParams`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct Joint {
+    #[cfg(feature = "parallel")]
+    pub(crate) constraint_index: usize,
+    #[cfg(feature = "parallel")]
+    pub(crate) position_constraint_index: usize,
+    /// The handle of this joint.
+    pub handle: JointHandle,
+    /// The first rigid-body attached to this joint.
+    pub body1: RigidBodyHandle,
+    /// The second rigid-body attached to this joint.
+    pub body2: RigidBodyHandle,
+    /// The parameters of this joint.
+    pub params: JointParams,
+    /// The constraint impulse (force or torque, depending on the joint) magnitude beyond which
+    /// this joint breaks, if any. A `None` threshold means the joint never breaks.
+    pub break_threshold: Option<Real>,
+    /// The magnitude of the constraint impulse accumulated over the last step.
+    ///
+    /// `solve_velocity_constraints` is responsible for updating this every step for every joint
+    /// type, summing the impulse actually applied along each of the joint's rows (including
+    /// `BallJoint`/`FixedJoint`'s rigid-anchor rows, which it solves directly); `remove_broken_joints`
+    /// only ever compares it against `break_threshold`, it never computes it.
+    pub(crate) accumulated_impulse: Real,
+}
+
+#[cfg(all(test, feature = "serde-serialize"))]
+mod tests {
+    use super::*;
+    use crate::dynamics::{IntegrationParameters, RigidBodySet};
+    use crate::dynamics::joint::{ConstraintRow, Jacobian, JointAnchors};
+
+    #[derive(Clone)]
+    struct NoopGenerator;
+
+    impl JointConstraintGenerator for NoopGenerator {
+        fn velocity_constraints(
+            &self,
+            _params: &IntegrationParameters,
+            _bodies: &RigidBodySet,
+            _anchors: &JointAnchors,
+        ) -> Vec<ConstraintRow> {
+            Vec::new()
+        }
+
+        fn position_error(&self, _bodies: &RigidBodySet) -> (Jacobian, Real) {
+            (
+                Jacobian {
+                    linear1: Default::default(),
+                    angular1: Default::default(),
+                    linear2: Default::default(),
+                    angular2: Default::default(),
+                },
+                0.0,
+            )
+        }
+
+        fn clone_box(&self) -> Box<dyn JointConstraintGenerator> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn custom_joint_params_fail_to_serialize_with_a_clear_error() {
+        let params = JointParams::Custom(Box::new(NoopGenerator));
+        let err = serde_json::to_string(&params).unwrap_err();
+        assert!(err.to_string().contains("cannot be serialized"));
+    }
+}
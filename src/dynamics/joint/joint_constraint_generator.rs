@@ -0,0 +1,73 @@
+use crate::dynamics::{IntegrationParameters, RigidBodySet};
+use crate::math::{AngVector, Isometry, Real, Vector};
+
+/// The world-space anchor frames of the two bodies attached by a joint.
+///
+/// This is the `anchors` argument passed to [`JointConstraintGenerator`]: it spares every
+/// custom joint from having to look up and re-derive the anchor placement itself.
+pub struct JointAnchors {
+    /// The anchor frame attached to the first body, in world space.
+    pub anchor1: Isometry<Real>,
+    /// The anchor frame attached to the second body, in world space.
+    pub anchor2: Isometry<Real>,
+}
+
+/// The Jacobian of a single constraint row, split into the linear and angular contribution
+/// applied to each of the two bodies connected by the joint.
+#[derive(Copy, Clone, Debug)]
+pub struct Jacobian {
+    /// The linear part of the Jacobian applied to the first body.
+    pub linear1: Vector<Real>,
+    /// The angular part of the Jacobian applied to the first body.
+    pub angular1: AngVector<Real>,
+    /// The linear part of the Jacobian applied to the second body.
+    pub linear2: Vector<Real>,
+    /// The angular part of the Jacobian applied to the second body.
+    pub angular2: AngVector<Real>,
+}
+
+/// A single row of a velocity constraint, as consumed by the velocity solver.
+///
+/// The solver accumulates an impulse along `jacobian`, biased by `rhs`, and clamped to
+/// `[min_impulse, max_impulse]` — exactly as it does for the rows generated internally for
+/// the built-in joint types.
+#[derive(Clone, Debug)]
+pub struct ConstraintRow {
+    /// The Jacobian along which the constraint impulse is applied.
+    pub jacobian: Jacobian,
+    /// The bias velocity (right-hand-side) of this constraint row.
+    pub rhs: Real,
+    /// The minimum impulse that can be applied along this row.
+    pub min_impulse: Real,
+    /// The maximum impulse that can be applied along this row.
+    pub max_impulse: Real,
+}
+
+/// Generates the constraint rows of a user-defined joint.
+///
+/// Implement this trait to express holonomic constraints that the built-in joint types
+/// (`BallJoint`, `FixedJoint`, `PrismaticJoint`, `RevoluteJoint`) cannot represent — gears,
+/// racks, curve-following sliders, soft springs, etc. — and plug them into the solver through
+/// `JointParams::Custom` without forking it.
+pub trait JointConstraintGenerator: Send + Sync {
+    /// Returns the velocity constraint rows for this joint.
+    ///
+    /// The velocity solver accumulates impulses along these rows every solver iteration, the
+    /// same way it does for the rows it derives internally from the built-in joint types.
+    fn velocity_constraints(
+        &self,
+        params: &IntegrationParameters,
+        bodies: &RigidBodySet,
+        anchors: &JointAnchors,
+    ) -> Vec<ConstraintRow>;
+
+    /// Returns the positional drift of this joint and its gradient, for the nonlinear
+    /// position-stabilization pass.
+    fn position_error(&self, bodies: &RigidBodySet) -> (Jacobian, Real);
+
+    /// Clones this generator into a new boxed trait object.
+    ///
+    /// Required because `JointParams` needs to be `Clone` and `Box<dyn JointConstraintGenerator>`
+    /// cannot derive it.
+    fn clone_box(&self) -> Box<dyn JointConstraintGenerator>;
+}
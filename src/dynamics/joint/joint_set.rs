@@ -1,10 +1,12 @@
-use super::Joint;
+use super::component_registry::JointComponents;
+use super::{Joint, JointLimits};
 use crate::geometry::{InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex};
 
 use crate::data::arena::Arena;
 use crate::data::{BundleSet, ComponentSet, ComponentSetMut};
 use crate::dynamics::{IslandManager, RigidBodyActivation, RigidBodyIds, RigidBodyType};
 use crate::dynamics::{JointParams, RigidBodyHandle};
+use crate::math::Real;
 
 /// The unique identifier of a joint added to the joint set.
 /// The unique identifier of a collider added to a collider set.
@@ -39,9 +41,22 @@ pub(crate) type JointGraphEdge = crate::data::graph::Edge<Joint>;
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 /// A set of joints that can be handled by a physics `World`.
+///
+/// Joints backed by `JointParams::Custom` (see `JointConstraintGenerator`) are edges of
+/// `joint_graph` just like the built-in joint types, so they participate in island
+/// activation/sleeping the same way: `insert` and `select_active_interactions` never match on
+/// the joint's variant.
 pub struct JointSet {
     joint_ids: Arena<TemporaryInteractionIndex>, // Map joint handles to edge ids on the graph.
     joint_graph: InteractionGraph<RigidBodyHandle, Joint>,
+    // Joints attached to at least one non-sleeping dynamic body, as of the last
+    // `select_active_interactions` call. `insert` appends new joints to it directly and
+    // `remove`/`remove_rigid_body` patch it in place for the edges they touch (see
+    // `patch_active_joints_on_removal`), but `select_active_interactions` itself still rebuilds
+    // it from `joint_graph` on every call — see its doc comment.
+    active_joints: Vec<JointIndex>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    components: JointComponents,
 }
 
 impl JointSet {
@@ -50,9 +65,44 @@ impl JointSet {
         Self {
             joint_ids: Arena::new(),
             joint_graph: InteractionGraph::new(),
+            active_joints: Vec::new(),
+            components: JointComponents::new(),
         }
     }
 
+    /// Registers `T` as a joint component type. Harmless to call more than once for the same `T`.
+    pub fn register_component<T: Clone + 'static>(&mut self) {
+        self.components.register::<T>();
+    }
+
+    /// Attaches a `T` component to the joint with the given handle.
+    ///
+    /// Does nothing if `handle` is invalid, or if `T` was never `register_component`ed.
+    pub fn set_component<T: Clone + 'static>(&mut self, handle: JointHandle, value: T) {
+        if self.contains(handle) {
+            self.components.set(handle.into_raw_parts().0, value);
+        }
+    }
+
+    /// Gets the `T` component attached to the joint with the given handle, if any.
+    pub fn component<T: Clone + 'static>(&self, handle: JointHandle) -> Option<&T> {
+        // `self.contains` rejects a stale generation, so a recycled slot can't leak another
+        // joint's component.
+        if !self.contains(handle) {
+            return None;
+        }
+        self.components.get(handle.into_raw_parts().0)
+    }
+
+    /// Gets a mutable reference to the `T` component attached to the joint with the given
+    /// handle, if any.
+    pub fn component_mut<T: Clone + 'static>(&mut self, handle: JointHandle) -> Option<&mut T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        self.components.get_mut(handle.into_raw_parts().0)
+    }
+
     /// The number of joints on this set.
     pub fn len(&self) -> usize {
         self.joint_graph.graph.edges.len()
@@ -137,6 +187,86 @@ impl JointSet {
             .map(|e| (e.weight.handle, &mut e.weight))
     }
 
+    /// Sets the target velocity of the motor of `handle`'s single degree of freedom.
+    ///
+    /// This only has an effect on `PrismaticJoint` and `RevoluteJoint`; it is a no-op for any
+    /// other joint type (or if `handle` is invalid).
+    pub fn set_motor_velocity(&mut self, handle: JointHandle, target_vel: Real, max_force: Real) {
+        if let Some(joint) = self.get_mut(handle) {
+            match &mut joint.params {
+                JointParams::PrismaticJoint(j) => {
+                    j.motor.target_vel = target_vel;
+                    j.motor.max_force = max_force;
+                }
+                JointParams::RevoluteJoint(j) => {
+                    j.motor.target_vel = target_vel;
+                    j.motor.max_force = max_force;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Sets the target position of the motor of `handle`'s single degree of freedom.
+    ///
+    /// Like `set_motor_velocity`, this also takes `max_force`: calling only this method (with
+    /// no prior `set_motor_velocity` call) is the common case for a pure position/spring motor,
+    /// and `motor.max_force` defaults to `Real::MAX` (the solver's "no motor configured"
+    /// sentinel), so without it the motor would never actually turn on.
+    ///
+    /// This only has an effect on `PrismaticJoint` and `RevoluteJoint`; it is a no-op for any
+    /// other joint type (or if `handle` is invalid).
+    pub fn set_motor_position(
+        &mut self,
+        handle: JointHandle,
+        target_pos: Real,
+        stiffness: Real,
+        damping: Real,
+        max_force: Real,
+    ) {
+        if let Some(joint) = self.get_mut(handle) {
+            match &mut joint.params {
+                JointParams::PrismaticJoint(j) => {
+                    j.motor.target_pos = target_pos;
+                    j.motor.stiffness = stiffness;
+                    j.motor.damping = damping;
+                    j.motor.max_force = max_force;
+                }
+                JointParams::RevoluteJoint(j) => {
+                    j.motor.target_pos = target_pos;
+                    j.motor.stiffness = stiffness;
+                    j.motor.damping = damping;
+                    j.motor.max_force = max_force;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Sets the position limits of `handle`'s single degree of freedom.
+    ///
+    /// This only has an effect on `PrismaticJoint` and `RevoluteJoint`; it is a no-op for any
+    /// other joint type (or if `handle` is invalid).
+    pub fn set_limits(&mut self, handle: JointHandle, min: Real, max: Real) {
+        if let Some(joint) = self.get_mut(handle) {
+            match &mut joint.params {
+                JointParams::PrismaticJoint(j) => j.limits = Some(JointLimits::new(min, max)),
+                JointParams::RevoluteJoint(j) => j.limits = Some(JointLimits::new(min, max)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Sets the constraint impulse magnitude beyond which `handle` breaks.
+    ///
+    /// Pass `None` to make the joint unbreakable (the default). Does nothing if `handle` is
+    /// invalid.
+    pub fn set_break_threshold(&mut self, handle: JointHandle, break_threshold: Option<Real>) {
+        if let Some(joint) = self.get_mut(handle) {
+            joint.break_threshold = break_threshold;
+        }
+    }
+
     // /// The set of joints as an array.
     // pub(crate) fn joints(&self) -> &[JointGraphEdge] {
     //     // self.joint_graph
@@ -177,6 +307,8 @@ impl JointSet {
             #[cfg(feature = "parallel")]
             position_constraint_index: 0,
             params: joint_params.into(),
+            break_threshold: None,
+            accumulated_impulse: 0.0,
         };
 
         let mut graph_index1 = bodies.index(joint.body1.0).joint_graph_index;
@@ -195,13 +327,47 @@ impl JointSet {
         }
 
         self.joint_ids[handle] = self.joint_graph.add_edge(graph_index1, graph_index2, joint);
+        // Newly-inserted joints are almost always attached to an awake body, so add it to the
+        // active-joints cache right away instead of forcing a full rebuild. New edges are
+        // appended to `joint_graph.graph.edges`, so its new last index is this joint's.
+        self.active_joints.push(self.joint_graph.graph.edges.len() - 1);
         JointHandle(handle)
     }
 
+    /// Returns `Some(island_index)` if `joint` currently connects to an awake dynamic body,
+    /// i.e. it should be considered by this step's solver.
+    fn active_island<Bodies>(bodies: &Bodies, joint: &Joint) -> Option<usize>
+    where
+        Bodies: ComponentSet<RigidBodyType>
+            + ComponentSet<RigidBodyActivation>
+            + ComponentSet<RigidBodyIds>,
+    {
+        let (status1, activation1, ids1): (&RigidBodyType, &RigidBodyActivation, &RigidBodyIds) =
+            bodies.index_bundle(joint.body1.0);
+        let (status2, activation2, ids2): (&RigidBodyType, &RigidBodyActivation, &RigidBodyIds) =
+            bodies.index_bundle(joint.body2.0);
+
+        if (status1.is_dynamic() || status2.is_dynamic())
+            && (!status1.is_dynamic() || !activation1.sleeping)
+            && (!status2.is_dynamic() || !activation2.sleeping)
+        {
+            Some(if !status1.is_dynamic() {
+                ids2.active_island_id
+            } else {
+                ids1.active_island_id
+            })
+        } else {
+            None
+        }
+    }
+
     /// Retrieve all the joints happening between two active bodies.
-    // NOTE: this is very similar to the code from NarrowPhase::select_active_interactions.
+    ///
+    /// `O(total joints)`: `JointSet` has no hook into `IslandManager`'s wake-up path, so there
+    /// is no sound signal short of a full rescan for "a body just woke up directly into an
+    /// already-active island" (a change in `islands.num_islands()` misses exactly that case).
     pub(crate) fn select_active_interactions<Bodies>(
-        &self,
+        &mut self,
         islands: &IslandManager,
         bodies: &Bodies,
         out: &mut Vec<Vec<JointIndex>>,
@@ -214,34 +380,19 @@ impl JointSet {
             out_island.clear();
         }
 
-        // FIXME: don't iterate through all the interactions.
-        for (i, edge) in self.joint_graph.graph.edges.iter().enumerate() {
-            let joint = &edge.weight;
-
-            let (status1, activation1, ids1): (
-                &RigidBodyType,
-                &RigidBodyActivation,
-                &RigidBodyIds,
-            ) = bodies.index_bundle(joint.body1.0);
-            let (status2, activation2, ids2): (
-                &RigidBodyType,
-                &RigidBodyActivation,
-                &RigidBodyIds,
-            ) = bodies.index_bundle(joint.body2.0);
-
-            if (status1.is_dynamic() || status2.is_dynamic())
-                && (!status1.is_dynamic() || !activation1.sleeping)
-                && (!status2.is_dynamic() || !activation2.sleeping)
-            {
-                let island_index = if !status1.is_dynamic() {
-                    ids2.active_island_id
-                } else {
-                    ids1.active_island_id
-                };
-
-                out[island_index].push(i);
-            }
-        }
+        self.active_joints.clear();
+        self.active_joints.extend(
+            self.joint_graph
+                .graph
+                .edges
+                .iter()
+                .enumerate()
+                .filter_map(|(i, edge)| {
+                    let island_index = Self::active_island(bodies, &edge.weight)?;
+                    out[island_index].push(i);
+                    Some(i)
+                }),
+        );
     }
 
     /// Removes a joint from this set.
@@ -262,6 +413,7 @@ impl JointSet {
     {
         let id = self.joint_ids.remove(handle.0)?;
         let endpoints = self.joint_graph.graph.edge_endpoints(id)?;
+        let removed_index: JointIndex = id.index();
 
         if wake_up {
             // Wake-up the bodies attached to this joint.
@@ -279,9 +431,67 @@ impl JointSet {
             self.joint_ids[edge.handle.0] = id;
         }
 
+        // Patch the cache in place instead of invalidating the whole thing: the only entries a
+        // single removal can stale are the one for the removed edge itself, and the one for
+        // whichever edge `remove_edge`'s swap-remove moved into its place.
+        self.patch_active_joints_on_removal(removed_index);
+        // The arena slot may be recycled for an unrelated joint later on.
+        self.components.remove_slot(handle.into_raw_parts().0);
+
         removed_joint
     }
 
+    /// Patches `active_joints` in place after `joint_graph`'s swap-remove of `removed_index`:
+    /// drops its own cache entry, and relabels whichever entry pointed at the edge that got
+    /// moved into its slot.
+    fn patch_active_joints_on_removal(&mut self, removed_index: JointIndex) {
+        let swapped_from = self.joint_graph.graph.edges.len();
+        self.active_joints.retain(|&idx| idx != removed_index);
+        if swapped_from != removed_index {
+            for idx in self.active_joints.iter_mut() {
+                if *idx == swapped_from {
+                    *idx = removed_index;
+                }
+            }
+        }
+    }
+
+    /// Removes every joint whose accumulated constraint impulse exceeds its break threshold,
+    /// returning the handles of the joints that were removed.
+    ///
+    /// This mirrors exactly what calling `remove(handle, islands, bodies, true)` for each
+    /// broken joint would do: the bodies that were held together reactivate.
+    pub fn remove_broken_joints<Bodies>(
+        &mut self,
+        islands: &mut IslandManager,
+        bodies: &mut Bodies,
+    ) -> Vec<JointHandle>
+    where
+        Bodies: ComponentSetMut<RigidBodyActivation>
+            + ComponentSet<RigidBodyType>
+            + ComponentSetMut<RigidBodyIds>,
+    {
+        let broken: Vec<JointHandle> = self
+            .joint_graph
+            .graph
+            .edges
+            .iter()
+            .map(|edge| &edge.weight)
+            .filter(|joint| {
+                joint
+                    .break_threshold
+                    .map_or(false, |threshold| joint.accumulated_impulse.abs() > threshold)
+            })
+            .map(|joint| joint.handle)
+            .collect();
+
+        for handle in &broken {
+            self.remove(*handle, islands, bodies, true);
+        }
+
+        broken
+    }
+
     pub(crate) fn remove_rigid_body<Bodies>(
         &mut self,
         deleted_id: RigidBodyGraphIndex,
@@ -304,13 +514,20 @@ impl JointSet {
                 .collect();
             for (h1, h2, to_delete_handle) in to_delete {
                 let to_delete_edge_id = self.joint_ids.remove(to_delete_handle.0).unwrap();
+                let removed_index: JointIndex = to_delete_edge_id.index();
                 self.joint_graph.graph.remove_edge(to_delete_edge_id);
+                self.components.remove_slot(to_delete_handle.into_raw_parts().0);
 
                 // Update the id of the edge which took the place of the deleted one.
                 if let Some(j) = self.joint_graph.graph.edge_weight_mut(to_delete_edge_id) {
                     self.joint_ids[j.handle.0] = to_delete_edge_id;
                 }
 
+                // Patch the cache in place for this one edge instead of invalidating the whole
+                // thing; with tens of thousands of mostly-sleeping joints, a body (and all its
+                // joints) being removed shouldn't force a full rescan of every other island.
+                self.patch_active_joints_on_removal(removed_index);
+
                 // Wake up the attached bodies.
                 islands.wake_up(bodies, h1, true);
                 islands.wake_up(bodies, h2, true);
@@ -326,3 +543,121 @@ impl JointSet {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::joint::BallJoint;
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::math::Point;
+
+    #[test]
+    fn patch_active_joints_on_removal_relabels_the_swapped_edge() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+        let mut islands = IslandManager::new();
+
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b3 = bodies.insert(RigidBodyBuilder::dynamic().build());
+
+        let h1 = joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+        let h2 = joints.insert(
+            &mut bodies,
+            b1,
+            b3,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+
+        let mut out = vec![Vec::new(); 1];
+        joints.select_active_interactions(&islands, &bodies, &mut out);
+        assert_eq!(joints.active_joints.len(), 2);
+
+        // `h1` is the edge at index 0; removing it makes `joint_graph`'s swap-remove move `h2`
+        // (the last edge) into slot 0. The cache must follow that relabelling instead of keeping
+        // a stale reference to index 1.
+        joints.remove(h1, &mut islands, &mut bodies, true);
+
+        let h2_index = joints.joint_ids[h2.0].index();
+        assert_eq!(joints.active_joints, vec![h2_index]);
+        assert_eq!(joints.get(h2).unwrap().handle, h2);
+    }
+
+    #[test]
+    fn component_roundtrips_through_set_and_get() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let handle = joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+
+        joints.register_component::<u32>();
+        joints.set_component(handle, 42u32);
+        assert_eq!(joints.component::<u32>(handle), Some(&42));
+
+        *joints.component_mut::<u32>(handle).unwrap() += 1;
+        assert_eq!(joints.component::<u32>(handle), Some(&43));
+    }
+
+    #[test]
+    fn unregistered_component_type_is_always_none() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let handle = joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+
+        // `set_component` is a no-op without a prior `register_component`.
+        joints.set_component(handle, 42u32);
+        assert_eq!(joints.component::<u32>(handle), None);
+    }
+
+    #[test]
+    fn component_is_unreachable_through_a_stale_handle() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+        let mut islands = IslandManager::new();
+
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b3 = bodies.insert(RigidBodyBuilder::dynamic().build());
+
+        joints.register_component::<u32>();
+        let h1 = joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+        joints.set_component(h1, 1u32);
+        joints.remove(h1, &mut islands, &mut bodies, true);
+
+        // Recycles `h1`'s arena slot into a new joint that never had a component set.
+        let h3 = joints.insert(
+            &mut bodies,
+            b1,
+            b3,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+
+        assert_eq!(joints.component::<u32>(h1), None);
+        assert_eq!(joints.component::<u32>(h3), None);
+    }
+}
@@ -0,0 +1,827 @@
+use super::{
+    BallJoint, ConstraintRow, FixedJoint, Jacobian, JointAnchors, JointHandle, JointLimits,
+    JointMotor, JointParams, JointSet, PrismaticJoint, RevoluteJoint,
+};
+use crate::dynamics::{IntegrationParameters, RigidBodyHandle, RigidBodySet};
+use crate::math::{AngVector, Real, Rotation, Vector};
+
+/// A conservative default Baumgarte-style stabilization factor for the one-sided limit rows
+/// below, consistent with the fraction of positional error usually corrected per step by
+/// sequential-impulse solvers.
+const LIMIT_ERP: Real = 0.2;
+
+/// Same role as `LIMIT_ERP`, for `BallJoint`/`FixedJoint`'s two-sided rigid-anchor rows.
+const ANCHOR_ERP: Real = 0.2;
+
+#[cfg(feature = "dim2")]
+fn ang_dot(a: AngVector<Real>, b: AngVector<Real>) -> Real {
+    a * b
+}
+
+#[cfg(feature = "dim3")]
+fn ang_dot(a: AngVector<Real>, b: AngVector<Real>) -> Real {
+    a.dot(&b)
+}
+
+/// The angular-Jacobian contribution of a single rotational degree of freedom around `axis`.
+///
+/// In 2D there is only ever one rotation axis (implicitly the Z axis), so the degree of freedom
+/// is the whole (scalar) angular velocity; in 3D it is `axis` itself, so that `ang_dot` projects
+/// a body's angular velocity onto it.
+#[cfg(feature = "dim2")]
+fn angular_dof(_axis: Vector<Real>) -> AngVector<Real> {
+    1.0
+}
+
+#[cfg(feature = "dim3")]
+fn angular_dof(axis: Vector<Real>) -> AngVector<Real> {
+    axis
+}
+
+/// The relative rotation from `rot1` to `rot2`, expressed as the signed angle around `axis`
+/// (assumed unit length and expressed in the same space as `rot1`/`rot2`).
+#[cfg(feature = "dim2")]
+fn relative_angle(rot1: Rotation<Real>, rot2: Rotation<Real>, _axis: Vector<Real>) -> Real {
+    // In 2D there is only ever one rotation axis, so the relative rotation's own angle already
+    // is the angle around it.
+    (rot1.inverse() * rot2).angle()
+}
+
+/// The relative rotation from `rot1` to `rot2`, expressed as the signed angle around `axis`
+/// (assumed unit length and expressed in the same space as `rot1`/`rot2`).
+///
+/// This is a swing-twist decomposition of the relative rotation: the quaternion's vector part
+/// is projected onto `axis` to isolate the twist component, whose angle is then recovered with
+/// the standard closed form `2 * atan2(projection, w)`.
+#[cfg(feature = "dim3")]
+fn relative_angle(rot1: Rotation<Real>, rot2: Rotation<Real>, axis: Vector<Real>) -> Real {
+    let rel = rot2 * rot1.inverse();
+    let coords = rel.quaternion().coords;
+    let proj = coords.x * axis.x + coords.y * axis.y + coords.z * axis.z;
+    2.0 * proj.atan2(coords.w)
+}
+
+/// The relative rotation from `rot1` to `rot2`, as a rotation vector (one component per world
+/// axis, magnitude the angle): `rot2 * rot1.inverse()`'s axis-angle representation in 3D, or
+/// just its signed angle in 2D (there is only one rotation axis).
+#[cfg(feature = "dim2")]
+fn relative_angle_error(rot1: Rotation<Real>, rot2: Rotation<Real>) -> AngVector<Real> {
+    (rot1.inverse() * rot2).angle()
+}
+
+#[cfg(feature = "dim3")]
+fn relative_angle_error(rot1: Rotation<Real>, rot2: Rotation<Real>) -> AngVector<Real> {
+    (rot2 * rot1.inverse()).scaled_axis()
+}
+
+/// Integrates `rot` by the small rotation `delta` (a rotation vector, one component per world
+/// axis, magnitude the angle) — the position-space counterpart of applying an angular velocity
+/// over one unit of time. Works unchanged in 2D and 3D: `Rotation::new` already takes exactly
+/// an `AngVector<Real>` in both (a scalar angle, or a 3D scaled axis).
+fn integrate_angular_correction(rot: Rotation<Real>, delta: AngVector<Real>) -> Rotation<Real> {
+    Rotation::new(delta) * rot
+}
+
+/// The world-space directions the rigid-anchor point rows below sum over: every translational
+/// degree of freedom (the rotational rows use `angular_dof_axes` instead).
+#[cfg(feature = "dim2")]
+fn world_axes() -> [Vector<Real>; 2] {
+    [Vector::x(), Vector::y()]
+}
+
+#[cfg(feature = "dim3")]
+fn world_axes() -> [Vector<Real>; 3] {
+    [Vector::x(), Vector::y(), Vector::z()]
+}
+
+/// The lever-arm contribution of a point offset `r` from a body's origin to the angular
+/// Jacobian of a point constraint row along `axis`, i.e. `r × axis` generalized to 2D, where
+/// the angular quantity is a scalar rather than a vector.
+#[cfg(feature = "dim2")]
+fn lever_arm(r: Vector<Real>, axis: Vector<Real>) -> AngVector<Real> {
+    r.x * axis.y - r.y * axis.x
+}
+
+#[cfg(feature = "dim3")]
+fn lever_arm(r: Vector<Real>, axis: Vector<Real>) -> AngVector<Real> {
+    r.cross(&axis)
+}
+
+/// A basis for the angular degrees of freedom: a single scalar DOF in 2D (there is only ever
+/// one rotation axis), or the three world axes in 3D.
+#[cfg(feature = "dim2")]
+fn angular_dof_axes() -> [AngVector<Real>; 1] {
+    [1.0]
+}
+
+#[cfg(feature = "dim3")]
+fn angular_dof_axes() -> [AngVector<Real>; 3] {
+    [Vector::x(), Vector::y(), Vector::z()]
+}
+
+/// The velocity this step's motor row should target: `motor.target_vel` directly, plus — once
+/// `motor.stiffness` is nonzero — a proportional pull towards `motor.target_pos`, softened by
+/// `motor.damping` the same way a spring-damper trades overshoot for response time.
+fn motor_target_velocity(motor: &JointMotor, rel: Real) -> Real {
+    motor.target_vel + motor.stiffness * (motor.target_pos - rel) / (1.0 + motor.damping)
+}
+
+/// The push-out sign (`1.0` to correct a `rel > limits.max` violation, `-1.0` for
+/// `rel < limits.min`) and Baumgarte-corrected bias of a one-sided limit row, or `None` if `rel` is
+/// currently within `[limits.min, limits.max]`.
+fn limit_violation(rel: Real, limits: JointLimits, dt: Real) -> Option<(Real, Real)> {
+    if rel > limits.max {
+        Some((1.0, LIMIT_ERP * (limits.max - rel) / dt))
+    } else if rel < limits.min {
+        Some((-1.0, LIMIT_ERP * (rel - limits.min) / dt))
+    } else {
+        None
+    }
+}
+
+impl JointSet {
+    /// Runs one velocity-solver pass over every joint's constraint rows, accumulating the
+    /// impulse applied along them into `Joint::accumulated_impulse`.
+    ///
+    /// `BallJoint`/`FixedJoint`'s rigid-anchor rows have no other solver pass in this crate, so
+    /// they are built and solved here directly (see `ball_joint_rows`/`fixed_joint_rows`).
+    /// `PrismaticJoint`/`RevoluteJoint`'s locked perpendicular degrees of freedom are assumed
+    /// solved by the main velocity solver elsewhere in the pipeline; this only drives the extra
+    /// rows this series added on top of them (the motor/limit rows, including `RevoluteJoint`'s
+    /// angular limit), since nothing else in the solver knows how to interpret those. `Custom`'s
+    /// rows come from its `JointConstraintGenerator`.
+    pub fn solve_velocity_constraints(
+        &mut self,
+        params: &IntegrationParameters,
+        bodies: &mut RigidBodySet,
+    ) {
+        for edge in self.joint_graph.graph.edges.iter_mut() {
+            let joint = &mut edge.weight;
+            let (body1, body2) = (joint.body1, joint.body2);
+
+            let rows = match &joint.params {
+                JointParams::BallJoint(joint) => {
+                    Self::ball_joint_rows(*joint, bodies, body1, body2, params)
+                }
+                JointParams::FixedJoint(joint) => {
+                    Self::fixed_joint_rows(*joint, bodies, body1, body2, params)
+                }
+                JointParams::Custom(generator) => {
+                    let generator = generator.clone_box();
+                    match Self::anchors(bodies, body1, body2) {
+                        Some(anchors) => generator.velocity_constraints(params, bodies, &anchors),
+                        None => continue,
+                    }
+                }
+                JointParams::PrismaticJoint(joint) => {
+                    Self::prismatic_rows(*joint, bodies, body1, body2, params)
+                }
+                JointParams::RevoluteJoint(joint) => {
+                    Self::revolute_rows(*joint, bodies, body1, body2, params)
+                }
+            };
+
+            let mut accumulated_impulse = 0.0;
+
+            for row in &rows {
+                accumulated_impulse += Self::solve_row(row, bodies, body1, body2).abs();
+            }
+
+            joint.accumulated_impulse = accumulated_impulse;
+        }
+    }
+
+    /// Builds this step's motor and limit rows for a `PrismaticJoint`'s single sliding degree
+    /// of freedom, in world space along `local_axis1` (anchor offsets are ignored, a
+    /// simplification reasonable for the usual case of near-coincident anchors).
+    fn prismatic_rows(
+        joint: PrismaticJoint,
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        params: &IntegrationParameters,
+    ) -> Vec<ConstraintRow> {
+        let (rb1, rb2) = match (bodies.get(body1), bodies.get(body2)) {
+            (Some(rb1), Some(rb2)) => (rb1, rb2),
+            _ => return Vec::new(),
+        };
+
+        let axis = rb1.position().rotation * joint.local_axis1;
+        let rel_pos =
+            (rb2.position().translation.vector - rb1.position().translation.vector).dot(&axis);
+        let zero_ang = AngVector::default();
+        let mut rows = Vec::new();
+
+        // `JointMotor::default()` leaves `max_force` at `Real::MAX` as a "no motor configured"
+        // sentinel; only inject a row once `set_motor_velocity`/`set_motor_position` narrowed it.
+        if joint.motor.max_force < Real::MAX {
+            let max_impulse = joint.motor.max_force * params.dt;
+            rows.push(ConstraintRow {
+                jacobian: Jacobian {
+                    linear1: -axis,
+                    angular1: zero_ang,
+                    linear2: axis,
+                    angular2: zero_ang,
+                },
+                rhs: -motor_target_velocity(&joint.motor, rel_pos),
+                min_impulse: -max_impulse,
+                max_impulse,
+            });
+        }
+
+        if let Some(limits) = joint.limits {
+            if let Some((sign, bias)) = limit_violation(rel_pos, limits, params.dt) {
+                rows.push(ConstraintRow {
+                    jacobian: Jacobian {
+                        linear1: axis * sign,
+                        angular1: zero_ang,
+                        linear2: -axis * sign,
+                        angular2: zero_ang,
+                    },
+                    rhs: bias,
+                    min_impulse: 0.0,
+                    max_impulse: Real::MAX,
+                });
+            }
+        }
+
+        rows
+    }
+
+    /// Builds this step's motor and limit rows for a `RevoluteJoint`'s single rotational
+    /// degree of freedom, around world-space `local_axis1`.
+    fn revolute_rows(
+        joint: RevoluteJoint,
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        params: &IntegrationParameters,
+    ) -> Vec<ConstraintRow> {
+        let (rb1, rb2) = match (bodies.get(body1), bodies.get(body2)) {
+            (Some(rb1), Some(rb2)) => (rb1, rb2),
+            _ => return Vec::new(),
+        };
+
+        let axis = rb1.position().rotation * joint.local_axis1;
+        let angular = angular_dof(axis);
+        let zero_lin = Vector::default();
+        let mut rows = Vec::new();
+
+        if joint.motor.max_force < Real::MAX {
+            let rel_angle = relative_angle(rb1.position().rotation, rb2.position().rotation, axis);
+            let max_impulse = joint.motor.max_force * params.dt;
+            rows.push(ConstraintRow {
+                jacobian: Jacobian {
+                    linear1: zero_lin,
+                    angular1: -angular,
+                    linear2: zero_lin,
+                    angular2: angular,
+                },
+                rhs: -motor_target_velocity(&joint.motor, rel_angle),
+                min_impulse: -max_impulse,
+                max_impulse,
+            });
+        }
+
+        if let Some(limits) = joint.limits {
+            let rel_angle = relative_angle(rb1.position().rotation, rb2.position().rotation, axis);
+            if let Some((sign, bias)) = limit_violation(rel_angle, limits, params.dt) {
+                rows.push(ConstraintRow {
+                    jacobian: Jacobian {
+                        linear1: zero_lin,
+                        angular1: angular * sign,
+                        linear2: zero_lin,
+                        angular2: -angular * sign,
+                    },
+                    rhs: bias,
+                    min_impulse: 0.0,
+                    max_impulse: Real::MAX,
+                });
+            }
+        }
+
+        rows
+    }
+
+    /// Returns the positional drift (and its Jacobian) of every `JointParams::Custom` joint, as
+    /// reported by `JointConstraintGenerator::position_error`.
+    ///
+    /// Exposed for callers that just want to inspect drift (e.g. diagnostics); `correct_custom_joint_positions`
+    /// is what actually feeds this into a position-stabilization pass.
+    pub fn custom_joint_position_errors(
+        &self,
+        bodies: &RigidBodySet,
+    ) -> Vec<(JointHandle, crate::dynamics::Jacobian, Real)> {
+        self.iter()
+            .filter_map(|(handle, joint)| match &joint.params {
+                JointParams::Custom(generator) => {
+                    let (jacobian, error) = generator.position_error(bodies);
+                    Some((handle, jacobian, error))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Runs a single nonlinear position-stabilization step over every `JointParams::Custom`
+    /// joint, directly correcting the positional drift `custom_joint_position_errors` reports.
+    ///
+    /// Nothing else in this crate runs a position-stabilization pass, so unlike the built-in
+    /// joint types' perpendicular degrees of freedom (assumed corrected elsewhere), `Custom`
+    /// joints would otherwise drift without limit under `solve_velocity_constraints` alone.
+    pub fn correct_custom_joint_positions(&self, bodies: &mut RigidBodySet) {
+        for (handle, jacobian, error) in self.custom_joint_position_errors(bodies) {
+            if let Some(joint) = self.get(handle) {
+                Self::apply_position_correction(&jacobian, error, bodies, joint.body1, joint.body2);
+            }
+        }
+    }
+
+    /// Nudges `body1`/`body2` directly in position space to remove `error` along `jacobian`,
+    /// the position-level counterpart of `solve_row`: same effective-mass math, but applied to
+    /// each body's `Isometry` instead of its velocity, and biased by `ANCHOR_ERP` (the same
+    /// partial-correction factor the velocity-level rigid-anchor rows use) rather than solved
+    /// to completion in one step.
+    fn apply_position_correction(
+        jacobian: &Jacobian,
+        error: Real,
+        bodies: &mut RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ) {
+        let (inv_mass1, inv_i1) = match bodies.get(body1) {
+            Some(rb) => (rb.effective_inv_mass(), rb.effective_world_inv_inertia_sqrt()),
+            None => return,
+        };
+        let (inv_mass2, inv_i2) = match bodies.get(body2) {
+            Some(rb) => (rb.effective_inv_mass(), rb.effective_world_inv_inertia_sqrt()),
+            None => return,
+        };
+
+        let ang1 = inv_i1 * jacobian.angular1;
+        let ang2 = inv_i2 * jacobian.angular2;
+        let eff_inv_mass = jacobian.linear1.dot(&jacobian.linear1.component_mul(&inv_mass1))
+            + ang_dot(ang1, ang1)
+            + jacobian.linear2.dot(&jacobian.linear2.component_mul(&inv_mass2))
+            + ang_dot(ang2, ang2);
+
+        if eff_inv_mass <= Real::EPSILON {
+            return;
+        }
+
+        let lambda = -ANCHOR_ERP * error / eff_inv_mass;
+
+        if let Some(rb1) = bodies.get_mut(body1) {
+            let mut pos = *rb1.position();
+            pos.translation.vector += jacobian.linear1.component_mul(&inv_mass1) * lambda;
+            pos.rotation = integrate_angular_correction(pos.rotation, inv_i1 * ang1 * lambda);
+            rb1.set_position(pos, true);
+        }
+        if let Some(rb2) = bodies.get_mut(body2) {
+            let mut pos = *rb2.position();
+            pos.translation.vector += jacobian.linear2.component_mul(&inv_mass2) * lambda;
+            pos.rotation = integrate_angular_correction(pos.rotation, inv_i2 * ang2 * lambda);
+            rb2.set_position(pos, true);
+        }
+    }
+
+    fn anchors(
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ) -> Option<JointAnchors> {
+        let rb1 = bodies.get(body1)?;
+        let rb2 = bodies.get(body2)?;
+        Some(JointAnchors {
+            anchor1: *rb1.position(),
+            anchor2: *rb2.position(),
+        })
+    }
+
+    /// The sequential-impulse impulse magnitude a row needs to drive `rel_vel + rhs` to zero,
+    /// clamped to `[min_impulse, max_impulse]`, or `0.0` if either body is missing or both are
+    /// effectively infinite-mass along this row (e.g. non-dynamic, or fully axis-locked).
+    ///
+    /// The impulse is `-(rel_vel + rhs) / eff_inv_mass`, where `eff_inv_mass` is the row's
+    /// effective inverse mass `J·M⁻¹·Jᵀ` summed over both bodies: skipping it (as a bare
+    /// `-(rel_vel + rhs)`) is only correct for unit mass/inertia, and silently under- or
+    /// over-drives every other body.
+    fn compute_row_impulse(
+        row: &ConstraintRow,
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ) -> Real {
+        let (v1, w1, inv_mass1, inv_i1) = match bodies.get(body1) {
+            Some(rb) => (
+                *rb.linvel(),
+                *rb.angvel(),
+                rb.effective_inv_mass(),
+                rb.effective_world_inv_inertia_sqrt(),
+            ),
+            None => return 0.0,
+        };
+        let (v2, w2, inv_mass2, inv_i2) = match bodies.get(body2) {
+            Some(rb) => (
+                *rb.linvel(),
+                *rb.angvel(),
+                rb.effective_inv_mass(),
+                rb.effective_world_inv_inertia_sqrt(),
+            ),
+            None => return 0.0,
+        };
+
+        let rel_vel = row.jacobian.linear1.dot(&v1)
+            + ang_dot(row.jacobian.angular1, w1)
+            + row.jacobian.linear2.dot(&v2)
+            + ang_dot(row.jacobian.angular2, w2);
+
+        // `inv_i1`/`inv_i2` are the square root of the (world-space) inverse inertia, so
+        // `angular^T · inv_i · angular` is computed as `|inv_i · angular|²` instead of needing
+        // the full inverse inertia tensor here.
+        let ang1 = inv_i1 * row.jacobian.angular1;
+        let ang2 = inv_i2 * row.jacobian.angular2;
+        let eff_inv_mass = row.jacobian.linear1.dot(&row.jacobian.linear1.component_mul(&inv_mass1))
+            + ang_dot(ang1, ang1)
+            + row.jacobian.linear2.dot(&row.jacobian.linear2.component_mul(&inv_mass2))
+            + ang_dot(ang2, ang2);
+
+        if eff_inv_mass <= Real::EPSILON {
+            return 0.0;
+        }
+
+        (-(rel_vel + row.rhs) / eff_inv_mass)
+            .max(row.min_impulse)
+            .min(row.max_impulse)
+    }
+
+    /// Solves a single constraint row with a sequential-impulse step, applying the resulting
+    /// impulse to both bodies, and returning its (signed) magnitude.
+    fn solve_row(
+        row: &ConstraintRow,
+        bodies: &mut RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ) -> Real {
+        let impulse = Self::compute_row_impulse(row, bodies, body1, body2);
+
+        if let Some(rb1) = bodies.get_mut(body1) {
+            rb1.apply_impulse(row.jacobian.linear1 * impulse, true);
+            rb1.apply_torque_impulse(row.jacobian.angular1 * impulse, true);
+        }
+        if let Some(rb2) = bodies.get_mut(body2) {
+            rb2.apply_impulse(row.jacobian.linear2 * impulse, true);
+            rb2.apply_torque_impulse(row.jacobian.angular2 * impulse, true);
+        }
+
+        impulse
+    }
+
+    /// Builds this step's rows for `BallJoint`'s rigid point-to-point anchor constraint: one
+    /// row per world axis, pulling `anchor2` onto `anchor1` with an `ANCHOR_ERP` Baumgarte bias
+    /// so persistent drift under sustained load (e.g. a body hanging in gravity) gets corrected
+    /// rather than just held at its current (already displaced) position.
+    fn ball_joint_rows(
+        joint: BallJoint,
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        params: &IntegrationParameters,
+    ) -> Vec<ConstraintRow> {
+        let (rb1, rb2) = match (bodies.get(body1), bodies.get(body2)) {
+            (Some(rb1), Some(rb2)) => (rb1, rb2),
+            _ => return Vec::new(),
+        };
+
+        let anchor1 = *rb1.position() * joint.local_anchor1;
+        let anchor2 = *rb2.position() * joint.local_anchor2;
+        let r1 = anchor1.coords - rb1.position().translation.vector;
+        let r2 = anchor2.coords - rb2.position().translation.vector;
+        let error = anchor2 - anchor1;
+
+        world_axes()
+            .into_iter()
+            .map(|axis| ConstraintRow {
+                jacobian: Jacobian {
+                    linear1: -axis,
+                    angular1: -lever_arm(r1, axis),
+                    linear2: axis,
+                    angular2: lever_arm(r2, axis),
+                },
+                rhs: ANCHOR_ERP * error.dot(&axis) / params.dt,
+                min_impulse: -Real::MAX,
+                max_impulse: Real::MAX,
+            })
+            .collect()
+    }
+
+    /// Builds this step's rows for `FixedJoint`'s rigid anchor-frame constraint: `ball_joint_rows`'
+    /// point-to-point rows, plus one angular row per world axis holding the anchor frames'
+    /// orientations together (via `relative_angle_error`), both `ANCHOR_ERP`-biased the same way.
+    fn fixed_joint_rows(
+        joint: FixedJoint,
+        bodies: &RigidBodySet,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        params: &IntegrationParameters,
+    ) -> Vec<ConstraintRow> {
+        let (rb1, rb2) = match (bodies.get(body1), bodies.get(body2)) {
+            (Some(rb1), Some(rb2)) => (rb1, rb2),
+            _ => return Vec::new(),
+        };
+
+        let world_frame1 = *rb1.position() * joint.local_frame1;
+        let world_frame2 = *rb2.position() * joint.local_frame2;
+        let r1 = world_frame1.translation.vector - rb1.position().translation.vector;
+        let r2 = world_frame2.translation.vector - rb2.position().translation.vector;
+        let error = world_frame2.translation.vector - world_frame1.translation.vector;
+
+        let mut rows: Vec<ConstraintRow> = world_axes()
+            .into_iter()
+            .map(|axis| ConstraintRow {
+                jacobian: Jacobian {
+                    linear1: -axis,
+                    angular1: -lever_arm(r1, axis),
+                    linear2: axis,
+                    angular2: lever_arm(r2, axis),
+                },
+                rhs: ANCHOR_ERP * error.dot(&axis) / params.dt,
+                min_impulse: -Real::MAX,
+                max_impulse: Real::MAX,
+            })
+            .collect();
+
+        let ang_error = relative_angle_error(world_frame1.rotation, world_frame2.rotation);
+        let zero_lin = Vector::default();
+        rows.extend(angular_dof_axes().into_iter().map(|dof| ConstraintRow {
+            jacobian: Jacobian {
+                linear1: zero_lin,
+                angular1: -dof,
+                linear2: zero_lin,
+                angular2: dof,
+            },
+            rhs: ANCHOR_ERP * ang_dot(ang_error, dof) / params.dt,
+            min_impulse: -Real::MAX,
+            max_impulse: Real::MAX,
+        }));
+
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::joint::JointConstraintGenerator;
+    use crate::dynamics::{IntegrationParameters, IslandManager, RigidBodyBuilder, RigidBodySet};
+    use crate::math::{Isometry, Point};
+
+    fn apply_gravity(bodies: &mut RigidBodySet, body: RigidBodyHandle, params: &IntegrationParameters) {
+        let rb = bodies.get_mut(body).unwrap();
+        let impulse = Vector::y() * (-9.81 * rb.mass() * params.dt);
+        rb.apply_impulse(impulse, true);
+    }
+
+    #[test]
+    fn hanging_ball_joint_breaks_under_sustained_load() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+        let mut islands = IslandManager::new();
+        let params = IntegrationParameters::default();
+
+        let anchor = bodies.insert(RigidBodyBuilder::fixed().build());
+        let hanging = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let handle = joints.insert(
+            &mut bodies,
+            anchor,
+            hanging,
+            BallJoint::new(Point::origin(), Point::origin()),
+        );
+        // Comfortably below the impulse sustaining `hanging`'s weight takes every step.
+        joints.set_break_threshold(handle, Some(0.01));
+
+        for _ in 0..10 {
+            apply_gravity(&mut bodies, hanging, &params);
+            joints.solve_velocity_constraints(&params, &mut bodies);
+        }
+
+        let broken = joints.remove_broken_joints(&mut islands, &mut bodies);
+        assert_eq!(broken, vec![handle]);
+        assert!(joints.get(handle).is_none());
+    }
+
+    #[test]
+    fn hanging_fixed_joint_breaks_under_sustained_load() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+        let mut islands = IslandManager::new();
+        let params = IntegrationParameters::default();
+
+        let anchor = bodies.insert(RigidBodyBuilder::fixed().build());
+        let hanging = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let handle = joints.insert(
+            &mut bodies,
+            anchor,
+            hanging,
+            FixedJoint::new(Isometry::identity(), Isometry::identity()),
+        );
+        joints.set_break_threshold(handle, Some(0.01));
+
+        for _ in 0..10 {
+            apply_gravity(&mut bodies, hanging, &params);
+            joints.solve_velocity_constraints(&params, &mut bodies);
+        }
+
+        let broken = joints.remove_broken_joints(&mut islands, &mut bodies);
+        assert_eq!(broken, vec![handle]);
+        assert!(joints.get(handle).is_none());
+    }
+
+    fn at(offset: Vector<Real>) -> Isometry<Real> {
+        let mut pos = Isometry::identity();
+        pos.translation.vector = offset;
+        pos
+    }
+
+    #[test]
+    fn prismatic_motor_row_targets_motor_velocity() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let params = IntegrationParameters::default();
+
+        let mut joint = PrismaticJoint::new(Point::origin(), Vector::x(), Point::origin(), Vector::x());
+        joint.motor.target_vel = 2.0;
+        joint.motor.max_force = 5.0;
+
+        let rows = JointSet::prismatic_rows(joint, &bodies, b1, b2, &params);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].rhs, -2.0);
+        assert_eq!(rows[0].max_impulse, 5.0 * params.dt);
+        assert_eq!(rows[0].min_impulse, -5.0 * params.dt);
+    }
+
+    #[test]
+    fn prismatic_limit_row_only_appears_past_the_bound() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let params = IntegrationParameters::default();
+
+        let mut joint = PrismaticJoint::new(Point::origin(), Vector::x(), Point::origin(), Vector::x());
+        joint.limits = Some(JointLimits::new(-1.0, 1.0));
+
+        // Both bodies coincide at the origin: well within `[-1.0, 1.0]`, no limit row.
+        let rows = JointSet::prismatic_rows(joint, &bodies, b1, b2, &params);
+        assert!(rows.is_empty());
+
+        // Push `b2` past the upper bound.
+        bodies.get_mut(b2).unwrap().set_position(at(Vector::x() * 2.0), true);
+        let rows = JointSet::prismatic_rows(joint, &bodies, b1, b2, &params);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].rhs < 0.0);
+    }
+
+    #[test]
+    fn revolute_limit_row_pushes_back_towards_the_bound() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let params = IntegrationParameters::default();
+
+        let mut joint = RevoluteJoint::new(Point::origin(), Vector::x(), Point::origin(), Vector::x());
+        joint.limits = Some(JointLimits::new(-0.1, 0.1));
+
+        let rows = JointSet::revolute_rows(joint, &bodies, b1, b2, &params);
+        assert!(rows.is_empty());
+
+        #[cfg(feature = "dim3")]
+        let rotated = Rotation::from_axis_angle(&Vector::x_axis(), 0.5);
+        #[cfg(feature = "dim2")]
+        let rotated = Rotation::new(0.5);
+        let mut pos = Isometry::identity();
+        pos.rotation = rotated;
+        bodies.get_mut(b2).unwrap().set_position(pos, true);
+
+        let rows = JointSet::revolute_rows(joint, &bodies, b1, b2, &params);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].rhs < 0.0);
+    }
+
+    #[derive(Clone)]
+    struct FixedRelativeVelocityGenerator {
+        target: Real,
+    }
+
+    impl JointConstraintGenerator for FixedRelativeVelocityGenerator {
+        fn velocity_constraints(
+            &self,
+            _params: &IntegrationParameters,
+            _bodies: &RigidBodySet,
+            _anchors: &JointAnchors,
+        ) -> Vec<ConstraintRow> {
+            vec![ConstraintRow {
+                jacobian: Jacobian {
+                    linear1: -Vector::x(),
+                    angular1: AngVector::default(),
+                    linear2: Vector::x(),
+                    angular2: AngVector::default(),
+                },
+                rhs: -self.target,
+                min_impulse: -Real::MAX,
+                max_impulse: Real::MAX,
+            }]
+        }
+
+        fn position_error(&self, _bodies: &RigidBodySet) -> (Jacobian, Real) {
+            (
+                Jacobian {
+                    linear1: Vector::default(),
+                    angular1: AngVector::default(),
+                    linear2: Vector::default(),
+                    angular2: AngVector::default(),
+                },
+                0.0,
+            )
+        }
+
+        fn clone_box(&self) -> Box<dyn JointConstraintGenerator> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn custom_joint_rows_are_solved_and_accumulated() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let mut joints = JointSet::new();
+        let params = IntegrationParameters::default();
+
+        let handle = joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            JointParams::Custom(Box::new(FixedRelativeVelocityGenerator { target: 3.0 })),
+        );
+
+        joints.solve_velocity_constraints(&params, &mut bodies);
+
+        let rel_vel = bodies.get(b2).unwrap().linvel().x - bodies.get(b1).unwrap().linvel().x;
+        assert!((rel_vel - 3.0).abs() < 1.0e-4);
+        assert!(joints.get(handle).unwrap().accumulated_impulse > 0.0);
+    }
+
+    #[derive(Clone)]
+    struct DriftGenerator {
+        error: Real,
+    }
+
+    impl JointConstraintGenerator for DriftGenerator {
+        fn velocity_constraints(
+            &self,
+            _params: &IntegrationParameters,
+            _bodies: &RigidBodySet,
+            _anchors: &JointAnchors,
+        ) -> Vec<ConstraintRow> {
+            Vec::new()
+        }
+
+        fn position_error(&self, _bodies: &RigidBodySet) -> (Jacobian, Real) {
+            (
+                Jacobian {
+                    linear1: -Vector::x(),
+                    angular1: AngVector::default(),
+                    linear2: Vector::x(),
+                    angular2: AngVector::default(),
+                },
+                self.error,
+            )
+        }
+
+        fn clone_box(&self) -> Box<dyn JointConstraintGenerator> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn custom_joint_position_drift_is_corrected() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let mut joints = JointSet::new();
+
+        joints.insert(
+            &mut bodies,
+            b1,
+            b2,
+            JointParams::Custom(Box::new(DriftGenerator { error: 1.0 })),
+        );
+
+        let before = bodies.get(b2).unwrap().position().translation.vector.x;
+        joints.correct_custom_joint_positions(&mut bodies);
+        let after = bodies.get(b2).unwrap().position().translation.vector.x;
+
+        assert_ne!(before, after);
+    }
+}
@@ -0,0 +1,23 @@
+//! Joints to constrain the relative motion of two rigid-bodies.
+
+pub use self::ball_joint::BallJoint;
+pub use self::fixed_joint::FixedJoint;
+pub use self::joint::{Joint, JointParams};
+pub use self::joint_constraint_generator::{ConstraintRow, Jacobian, JointAnchors, JointConstraintGenerator};
+pub use self::joint_set::{JointHandle, JointSet};
+pub use self::motor::{JointLimits, JointMotor};
+pub use self::prismatic_joint::PrismaticJoint;
+pub use self::revolute_joint::RevoluteJoint;
+
+pub(crate) use self::joint_set::{JointGraphEdge, JointIndex};
+
+mod ball_joint;
+mod component_registry;
+mod fixed_joint;
+mod joint;
+mod joint_constraint_generator;
+mod joint_set;
+mod joint_velocity_solver;
+mod motor;
+mod prismatic_joint;
+mod revolute_joint;
@@ -0,0 +1,54 @@
+use crate::math::Real;
+
+/// A motor driving a single degree of freedom of a joint towards a target velocity or position.
+///
+/// The solver clamps the motor impulse to `[-max_force * dt, max_force * dt]` each step, and
+/// blends the velocity and position objectives by adding a `stiffness`-scaled pull towards
+/// `target_pos` on top of `target_vel`, softened by `damping` the same way a spring-damper
+/// trades overshoot for response time (`stiffness == 0.0` disables position driving entirely).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct JointMotor {
+    /// The target relative velocity the motor drives the degree of freedom towards.
+    pub target_vel: Real,
+    /// The target relative position the motor drives the degree of freedom towards.
+    pub target_pos: Real,
+    /// The spring-like stiffness used to reach `target_pos`. Zero disables position driving.
+    pub stiffness: Real,
+    /// The spring-like damping used to reach `target_pos`.
+    pub damping: Real,
+    /// The maximum force (or torque, for angular degrees of freedom) the motor can apply.
+    pub max_force: Real,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            target_vel: 0.0,
+            target_pos: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            max_force: Real::MAX,
+        }
+    }
+}
+
+/// The `[min, max]` position limits of a single degree of freedom of a joint.
+///
+/// The solver injects this as a one-sided constraint that is slack while the degree of freedom
+/// stays within bounds, and only pushes back once it reaches `min` or `max`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct JointLimits {
+    /// The minimum position allowed for the degree of freedom.
+    pub min: Real,
+    /// The maximum position allowed for the degree of freedom.
+    pub max: Real,
+}
+
+impl JointLimits {
+    /// Creates a new set of limits, clamping the degree of freedom between `min` and `max`.
+    pub fn new(min: Real, max: Real) -> Self {
+        Self { min, max }
+    }
+}
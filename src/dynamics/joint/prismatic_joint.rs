@@ -0,0 +1,48 @@
+use super::{JointLimits, JointMotor, JointParams};
+use crate::math::{Point, Real, Vector};
+
+/// A prismatic joint lets a body slide relative to the other along a single axis.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct PrismaticJoint {
+    /// The anchor point on the first body, expressed in its local space.
+    pub local_anchor1: Point<Real>,
+    /// The sliding axis, expressed in the local space of the first body. Must be normalized.
+    pub local_axis1: Vector<Real>,
+    /// The anchor point on the second body, expressed in its local space.
+    pub local_anchor2: Point<Real>,
+    /// The sliding axis, expressed in the local space of the second body. Must be normalized.
+    pub local_axis2: Vector<Real>,
+    /// The motor driving the sliding degree of freedom of this joint.
+    pub motor: JointMotor,
+    /// The position limits of the sliding degree of freedom of this joint, if any.
+    pub limits: Option<JointLimits>,
+}
+
+impl PrismaticJoint {
+    /// Creates a new prismatic joint with the given anchors and sliding axes, expressed in the
+    /// local space of each of the affected bodies.
+    ///
+    /// The joint has no motor and no limits by default.
+    pub fn new(
+        local_anchor1: Point<Real>,
+        local_axis1: Vector<Real>,
+        local_anchor2: Point<Real>,
+        local_axis2: Vector<Real>,
+    ) -> Self {
+        Self {
+            local_anchor1,
+            local_axis1,
+            local_anchor2,
+            local_axis2,
+            motor: JointMotor::default(),
+            limits: None,
+        }
+    }
+}
+
+impl From<PrismaticJoint> for JointParams {
+    fn from(joint: PrismaticJoint) -> JointParams {
+        JointParams::PrismaticJoint(joint)
+    }
+}
@@ -13,10 +13,19 @@
 //! ### Default gravity on earth
 //!
 //! If you just want to use earths default gravity (9.81m/s²), you can use `Uniform::default()`.
+//!
+//! ## Point gravity
+//!
+//! If you want an inverse-square attractor like a planet or a star, use `PointGravity`.
+//!
+//! ## Combining several sources
+//!
+//! If you want several gravity sources to apply at once (e.g. more than one planet), combine
+//! them with a `GravityField`.
 
 use std::ops::Deref;
 
-use crate::math::{Isometry, Real, Vector};
+use crate::math::{Isometry, Point, Real, Vector};
 
 /// Trait for calculating gravity at a given point
 pub trait Gravity {
@@ -83,4 +92,98 @@ impl<'a> Gravity for &'a Vector<Real> {
     fn force_at(&self, _position: &Isometry<Real>, mass: Real, scale: Real) -> Vector<Real> {
         *self.clone() * mass * scale
     }
+}
+
+/// Gravity pulling bodies towards a single point, with an inverse-square falloff.
+///
+/// This models a planet, a star, or any other point-like attractor.
+pub struct PointGravity {
+    /// The position of the attractor.
+    pub center: Point<Real>,
+    /// The attractor's standard gravitational parameter (`G * mass`).
+    pub mu: Real,
+    /// Softens the force close to `center`, preventing it from blowing up when a body passes
+    /// through the attractor.
+    pub softening: Real,
+}
+
+impl PointGravity {
+    /// Creates a new point gravity centered at `center`, with the given gravitational
+    /// parameter `mu` (`G * mass`) and `softening` distance.
+    pub fn new(center: Point<Real>, mu: Real, softening: Real) -> Self {
+        Self {
+            center,
+            mu,
+            softening,
+        }
+    }
+}
+
+impl Gravity for PointGravity {
+    fn force_at(&self, position: &Isometry<Real>, mass: Real, scale: Real) -> Vector<Real> {
+        let dir = self.center.coords - position.translation.vector;
+        let dist_sq = dir.norm_squared() + self.softening * self.softening;
+        dir * (self.mu * mass * scale / (dist_sq * dist_sq.sqrt()))
+    }
+}
+
+/// Combines several gravity sources into a single field by summing their contributions.
+///
+/// Use this to model several attractors (e.g. more than one planet) acting on the same scene
+/// at once.
+pub struct GravityField(pub Vec<Box<dyn Gravity>>);
+
+impl GravityField {
+    /// Creates a new gravity field combining the given sources.
+    pub fn new(sources: Vec<Box<dyn Gravity>>) -> Self {
+        Self(sources)
+    }
+}
+
+impl Gravity for GravityField {
+    fn force_at(&self, position: &Isometry<Real>, mass: Real, scale: Real) -> Vector<Real> {
+        self.0
+            .iter()
+            .fold(Vector::default(), |acc, source| {
+                acc + source.force_at(position, mass, scale)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_distance(dist: Real) -> Isometry<Real> {
+        let mut position = Isometry::identity();
+        position.translation.vector = Vector::x() * dist;
+        position
+    }
+
+    #[test]
+    fn point_gravity_follows_inverse_square_law() {
+        let gravity = PointGravity::new(Point::origin(), 100.0, 0.0);
+
+        let force_near = gravity.force_at(&at_distance(1.0), 1.0, 1.0);
+        let force_far = gravity.force_at(&at_distance(2.0), 1.0, 1.0);
+
+        // Doubling the distance should quarter the force's magnitude.
+        assert!((force_near.norm() / force_far.norm() - 4.0).abs() < 1.0e-6);
+        // The force always points from the body towards the attractor.
+        assert!(force_near.x < 0.0);
+    }
+
+    #[test]
+    fn point_gravity_softening_bounds_the_force_at_the_center() {
+        let gravity = PointGravity::new(Point::origin(), 100.0, 1.0);
+        let force = gravity.force_at(&at_distance(0.0), 1.0, 1.0);
+        assert_eq!(force.norm(), 0.0);
+
+        let softened = PointGravity::new(Point::origin(), 100.0, 0.5);
+        let unsoftened = PointGravity::new(Point::origin(), 100.0, 0.0);
+        assert!(
+            softened.force_at(&at_distance(0.1), 1.0, 1.0).norm()
+                < unsoftened.force_at(&at_distance(0.1), 1.0, 1.0).norm()
+        );
+    }
 }
\ No newline at end of file